@@ -6,22 +6,23 @@
 //!
 //! ```
 //! # use ws04::coordinate::Coordinate;
-//! let position = Coordinate::new(3, 4);
+//! let position = Coordinate::new(3.0, 4.0);
 //!
 //! ```
 
 use crate::directions::direction::Vector;
+use serde::{Deserialize, Serialize};
 use std::convert::From;
 use std::default::Default;
 use std::ops::{Add, AddAssign, Sub, SubAssign};
 
 /// Represent a 2D coordinate.
-#[derive(Debug, Eq, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct Coordinate {
     /// x coordinate
-    pub x: i32,
+    pub x: f64,
     /// y coordinate
-    pub y: i32,
+    pub y: f64,
 }
 
 impl Coordinate {
@@ -31,7 +32,7 @@ impl Coordinate {
     ///
     /// - `x` - The x coordinate of the new position.
     /// - `y` - The y coordinate of the new position.
-    pub fn new(x: i32, y: i32) -> Coordinate {
+    pub fn new(x: f64, y: f64) -> Coordinate {
         Coordinate { x, y }
     }
 
@@ -63,13 +64,13 @@ impl Coordinate {
     ///
     /// ```
     /// use ws04::coordinate::Coordinate;
-    /// let top_left = Coordinate::new(1, 1);
-    /// let bottom_right = Coordinate::new(5, 5);
+    /// let top_left = Coordinate::new(1.0, 1.0);
+    /// let bottom_right = Coordinate::new(5.0, 5.0);
     ///
-    /// assert!(Coordinate::new(1, 1).in_rectangle(&top_left, &bottom_right));
-    /// assert!(Coordinate::new(1, 5).in_rectangle(&top_left, &bottom_right));
-    /// assert!(Coordinate::new(3, 3).in_rectangle(&top_left, &bottom_right));
-    /// assert!(Coordinate::new(5, 5).in_rectangle(&top_left, &bottom_right));
+    /// assert!(Coordinate::new(1.0, 1.0).in_rectangle(&top_left, &bottom_right));
+    /// assert!(Coordinate::new(1.0, 5.0).in_rectangle(&top_left, &bottom_right));
+    /// assert!(Coordinate::new(3.0, 3.0).in_rectangle(&top_left, &bottom_right));
+    /// assert!(Coordinate::new(5.0, 5.0).in_rectangle(&top_left, &bottom_right));
     /// ```
     ///
     /// The rectangle can be inferred even if you don't specify the top left and
@@ -78,13 +79,13 @@ impl Coordinate {
     ///
     /// ```
     /// # use ws04::coordinate::Coordinate;
-    /// let bottom_left = Coordinate::new(1, 5);
-    /// let top_right = Coordinate::new(5, 1);
+    /// let bottom_left = Coordinate::new(1.0, 5.0);
+    /// let top_right = Coordinate::new(5.0, 1.0);
     ///
-    /// assert!(Coordinate::new(1, 1).in_rectangle(&bottom_left, &top_right));
-    /// assert!(Coordinate::new(1, 5).in_rectangle(&bottom_left, &top_right));
-    /// assert!(Coordinate::new(3, 3).in_rectangle(&bottom_left, &top_right));
-    /// assert!(Coordinate::new(5, 5).in_rectangle(&bottom_left, &top_right));
+    /// assert!(Coordinate::new(1.0, 1.0).in_rectangle(&bottom_left, &top_right));
+    /// assert!(Coordinate::new(1.0, 5.0).in_rectangle(&bottom_left, &top_right));
+    /// assert!(Coordinate::new(3.0, 3.0).in_rectangle(&bottom_left, &top_right));
+    /// assert!(Coordinate::new(5.0, 5.0).in_rectangle(&bottom_left, &top_right));
     /// ```
     pub fn in_rectangle(&self, a: &Self, b: &Self) -> bool {
         self.x_in_range(a, b)
@@ -95,8 +96,8 @@ impl Coordinate {
     ///
     /// ```
     /// use ws04::coordinate::Coordinate;
-    /// let c1 = Coordinate::new(4, 5);
-    /// let c2 = Coordinate::new(1, 1);
+    /// let c1 = Coordinate::new(4.0, 5.0);
+    /// let c2 = Coordinate::new(1.0, 1.0);
     /// assert_eq!(c1.scalar_difference(&c2), 5f64);
     /// ```
     pub fn scalar_difference(&self, other: &Self) -> f64 {
@@ -109,7 +110,7 @@ impl Default for Coordinate {
     ///
     /// This has zero values for x and y
     fn default() -> Self {
-        Coordinate { x: 0, y: 0 }
+        Coordinate { x: 0.0, y: 0.0 }
     }
 }
 
@@ -1,15 +1,16 @@
 //! This module represents a vector with an x and y coordinate. These can be
 //! added to our [`crate::coordinate::Coordinate`] values.
 
+use serde::{Deserialize, Serialize};
 use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
 
-#[derive(Debug, Eq, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 /// Struct representing a Vector of motion.
 pub struct Vector {
     /// x direction
-    pub x: i32,
+    pub x: f64,
     /// y direction
-    pub y: i32,
+    pub y: f64,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -21,16 +22,57 @@ pub enum CardinalDirection {
     West,
 }
 
+impl CardinalDirection {
+    /// Returns the unit [`Vector`] that points in this direction.
+    ///
+    /// ```
+    /// # use simulator_lib::directions::direction::{CardinalDirection, Vector};
+    /// assert_eq!(CardinalDirection::East.to_vector(), Vector::new(1.0, 0.0));
+    /// ```
+    pub fn to_vector(&self) -> Vector {
+        match self {
+            CardinalDirection::North => Vector::new(0.0, 1.0),
+            CardinalDirection::East => Vector::new(1.0, 0.0),
+            CardinalDirection::South => Vector::new(0.0, -1.0),
+            CardinalDirection::West => Vector::new(-1.0, 0.0),
+        }
+    }
+}
+
+impl From<&Vector> for CardinalDirection {
+    /// Buckets a vector's heading into the nearest cardinal direction.
+    ///
+    /// ```
+    /// # use simulator_lib::directions::direction::{CardinalDirection, Vector};
+    /// let heading = CardinalDirection::from(&Vector::new(0.0, 5.0));
+    /// assert_eq!(heading, CardinalDirection::North);
+    /// ```
+    fn from(vector: &Vector) -> CardinalDirection {
+        let angle = vector.y.atan2(vector.x);
+        let quarter_turn = std::f64::consts::FRAC_PI_4;
+
+        if angle >= -quarter_turn && angle < quarter_turn {
+            CardinalDirection::East
+        } else if angle >= quarter_turn && angle < 3.0 * quarter_turn {
+            CardinalDirection::North
+        } else if angle <= -quarter_turn && angle > -3.0 * quarter_turn {
+            CardinalDirection::South
+        } else {
+            CardinalDirection::West
+        }
+    }
+}
+
 impl Vector {
     /// Create a new vector.
     ///
     /// ```
     /// # use ws04::direction::Vector;
-    /// let v = Vector::new(3, 4);
-    /// assert_eq!(v.x, 3);
-    /// assert_eq!(v.y, 4);
+    /// let v = Vector::new(3.0, 4.0);
+    /// assert_eq!(v.x, 3.0);
+    /// assert_eq!(v.y, 4.0);
     /// ```
-    pub fn new(x: i32, y: i32) -> Vector {
+    pub fn new(x: f64, y: f64) -> Vector {
         Vector { x, y }
     }
 
@@ -38,11 +80,99 @@ impl Vector {
     ///
     /// ```
     /// # use ws04::direction::Vector;
-    /// let v = Vector::new(3, 4);
+    /// let v = Vector::new(3.0, 4.0);
     /// assert_eq!(v.magnitude(), 5f64);
     /// ```
     pub fn magnitude(&self) -> f64 {
-        (f64::from(self.x).powi(2) + f64::from(self.y).powi(2)).sqrt()
+        (self.x.powi(2) + self.y.powi(2)).sqrt()
+    }
+
+    /// Returns the dot product of this vector with another.
+    ///
+    /// ```
+    /// # use simulator_lib::directions::direction::Vector;
+    /// let a = Vector::new(1.0, 2.0);
+    /// let b = Vector::new(3.0, 4.0);
+    /// assert_eq!(a.dot(&b), 11.0);
+    /// ```
+    pub fn dot(&self, other: &Vector) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// Returns the scalar z-component of the cross product of this vector
+    /// with another, treating both as lying in the z=0 plane.
+    ///
+    /// ```
+    /// # use simulator_lib::directions::direction::Vector;
+    /// let a = Vector::new(1.0, 0.0);
+    /// let b = Vector::new(0.0, 1.0);
+    /// assert_eq!(a.cross(&b), 1.0);
+    /// ```
+    pub fn cross(&self, other: &Vector) -> f64 {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// Returns this vector scaled to unit length.
+    ///
+    /// ```
+    /// # use simulator_lib::directions::direction::Vector;
+    /// let v = Vector::new(3.0, 4.0).normalize();
+    /// assert_eq!(v.magnitude(), 1.0);
+    /// ```
+    pub fn normalize(&self) -> Vector {
+        let magnitude = self.magnitude();
+        Vector {
+            x: self.x / magnitude,
+            y: self.y / magnitude,
+        }
+    }
+
+    /// Returns the projection of this vector onto another: the component of
+    /// `self` that points along `other`.
+    ///
+    /// ```
+    /// # use simulator_lib::directions::direction::Vector;
+    /// let a = Vector::new(2.0, 3.0);
+    /// let b = Vector::new(1.0, 0.0);
+    /// assert_eq!(a.project_on(&b), Vector::new(2.0, 0.0));
+    /// ```
+    pub fn project_on(&self, other: &Vector) -> Vector {
+        let scale = self.dot(other) / other.dot(other);
+        Vector {
+            x: other.x * scale,
+            y: other.y * scale,
+        }
+    }
+
+    /// Returns the signed angle in radians from this vector to another,
+    /// measured counter-clockwise over the full circle.
+    ///
+    /// ```
+    /// # use simulator_lib::directions::direction::Vector;
+    /// # use std::f64::consts::FRAC_PI_2;
+    /// let a = Vector::new(1.0, 0.0);
+    /// let b = Vector::new(0.0, 1.0);
+    /// assert_eq!(a.angle_between(&b), FRAC_PI_2);
+    /// ```
+    pub fn angle_between(&self, other: &Vector) -> f64 {
+        self.cross(other).atan2(self.dot(other))
+    }
+
+    /// Returns this vector rotated counter-clockwise by the given angle, in
+    /// radians.
+    ///
+    /// ```
+    /// # use simulator_lib::directions::direction::Vector;
+    /// # use std::f64::consts::FRAC_PI_2;
+    /// let v = Vector::new(1.0, 0.0).rotate(FRAC_PI_2);
+    /// assert!((v.x - 0.0).abs() < 1e-10);
+    /// assert!((v.y - 1.0).abs() < 1e-10);
+    /// ```
+    pub fn rotate(&self, radians: f64) -> Vector {
+        Vector {
+            x: self.x * radians.cos() - self.y * radians.sin(),
+            y: self.x * radians.sin() + self.y * radians.cos(),
+        }
     }
 }
 
@@ -118,12 +248,12 @@ impl SubAssign for Vector {
     }
 }
 
-impl Mul<i32> for Vector {
+impl Mul<f64> for Vector {
     /// The resulting type after the `*` operator.
     type Output = Vector;
 
     /// Performs the `*` operation. See [`std::ops::Mul`]
-    fn mul(self, rhs: i32) -> Self::Output {
+    fn mul(self, rhs: f64) -> Self::Output {
         Self::Output {
             x: self.x * rhs,
             y: self.y * rhs,
@@ -131,12 +261,12 @@ impl Mul<i32> for Vector {
     }
 }
 
-impl MulAssign<i32> for Vector {
+impl MulAssign<f64> for Vector {
     /// Performs the `*=` operation. See [`std::ops::MulAssign`]
-    fn mul_assign(&mut self, rhs: i32) {
+    fn mul_assign(&mut self, rhs: f64) {
         *self = Vector {
             x: self.x * rhs,
             y: self.y * rhs,
         }
     }
-}
\ No newline at end of file
+}
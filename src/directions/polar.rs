@@ -0,0 +1,112 @@
+//! This module represents a point in polar coordinates: a distance `r` from
+//! the origin and an angle `theta` in radians. Conversion to and from the
+//! cartesian [`crate::directions::coordinate::Coordinate`] and
+//! [`crate::directions::direction::Vector`] types goes through [`Vector`].
+//!
+//! # Sample usage
+//!
+//! ```
+//! # use simulator_lib::directions::polar::Polar;
+//! # use simulator_lib::directions::direction::Vector;
+//! let heading = Polar::from(Vector::new(3.0, 4.0));
+//!
+//! ```
+
+use crate::directions::coordinate::Coordinate;
+use crate::directions::direction::Vector;
+use std::ops::{Add, Sub};
+
+/// Represents a point in polar coordinates.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Polar {
+    /// Distance from the origin.
+    pub r: f64,
+    /// Angle from the positive x-axis, in radians.
+    pub theta: f64,
+}
+
+impl Polar {
+    /// Create a new polar coordinate.
+    ///
+    /// # Arguments
+    ///
+    /// - `r` - The distance from the origin.
+    /// - `theta` - The angle from the positive x-axis, in radians.
+    pub fn new(r: f64, theta: f64) -> Polar {
+        Polar { r, theta }
+    }
+}
+
+impl From<Vector> for Polar {
+    /// Converts a cartesian vector to polar form.
+    ///
+    /// ```
+    /// # use simulator_lib::directions::direction::Vector;
+    /// # use simulator_lib::directions::polar::Polar;
+    /// let p = Polar::from(Vector::new(3.0, 4.0));
+    /// assert_eq!(p.r, 5.0);
+    /// ```
+    fn from(vector: Vector) -> Polar {
+        Polar {
+            r: vector.magnitude(),
+            theta: vector.y.atan2(vector.x),
+        }
+    }
+}
+
+impl From<Polar> for Vector {
+    /// Converts a polar coordinate back to a cartesian vector.
+    ///
+    /// ```
+    /// # use simulator_lib::directions::direction::Vector;
+    /// # use simulator_lib::directions::polar::Polar;
+    /// let v = Vector::from(Polar::new(5.0, 0.0));
+    /// assert_eq!(v, Vector::new(5.0, 0.0));
+    /// ```
+    fn from(polar: Polar) -> Vector {
+        Vector {
+            x: polar.r * polar.theta.cos(),
+            y: polar.r * polar.theta.sin(),
+        }
+    }
+}
+
+impl From<Coordinate> for Polar {
+    /// Converts a cartesian coordinate to polar form, treating it as a
+    /// vector from the origin.
+    fn from(coordinate: Coordinate) -> Polar {
+        Polar::from(Vector {
+            x: coordinate.x,
+            y: coordinate.y,
+        })
+    }
+}
+
+impl From<Polar> for Coordinate {
+    /// Converts a polar coordinate back to a cartesian coordinate.
+    fn from(polar: Polar) -> Coordinate {
+        Coordinate::from(Vector::from(polar))
+    }
+}
+
+impl Add for Polar {
+    /// The resulting type after the `+` operator.
+    type Output = Polar;
+
+    /// Performs the `+` operation by converting both sides to cartesian
+    /// vectors, adding them, and converting back to polar form.
+    fn add(self, rhs: Polar) -> Self::Output {
+        Polar::from(Vector::from(self) + Vector::from(rhs))
+    }
+}
+
+impl Sub for Polar {
+    /// The resulting type after the `-` operator.
+    type Output = Polar;
+
+    /// Performs the `-` operation by converting both sides to cartesian
+    /// vectors, subtracting them, and converting back to polar form.
+    fn sub(self, rhs: Polar) -> Self::Output {
+        Polar::from(Vector::from(self) - Vector::from(rhs))
+    }
+}
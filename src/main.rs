@@ -3,23 +3,27 @@ use simulator_lib::directions::{
     coordinate::Coordinate,
     direction::Vector
 };
+use std::time::Duration;
 fn main() {
-    let mut objects = vec![
+    let objects = vec![
         ObjectType::Planet(Planet {
-            coordinate: Coordinate::new(500, 500),
+            coordinate: Coordinate::new(500.0, 500.0),
             weight: 50,
+            velocity: Vector {x: 0.0, y: 0.0},
         }),
         ObjectType::Asteroid(Asteroid {
-            coordinate: Coordinate::new(250, 250),
-            velocity: Vector {x: 30, y: -10},
+            coordinate: Coordinate::new(250.0, 250.0),
+            velocity: Vector {x: 30.0, y: -10.0},
+            weight: 1,
         }),
         ObjectType::Asteroid(Asteroid {
-            coordinate: Coordinate::new(750, 750),
-            velocity: Vector {x: -30, y: 10},
+            coordinate: Coordinate::new(750.0, 750.0),
+            velocity: Vector {x: -30.0, y: 10.0},
+            weight: 1,
         }),
     ];
 
-    start_server("0.0.0.0:16991", objects, 70);
+    start_server("0.0.0.0:16991", objects, 70.0, 1e-3, Duration::from_millis(16));
 
 
 